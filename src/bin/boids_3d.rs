@@ -0,0 +1,280 @@
+// 3D counterpart to the flat-field boids in `main.rs`, backed by `KDTree3`
+use bevy::{
+    math::Vec3,
+    prelude::*,
+    render::{mesh::*, render_asset::RenderAssetUsages},
+    tasks::ComputeTaskPool,
+};
+use bevy_spatial::{kdtree::KDTree3, AutomaticUpdate, SpatialAccess, SpatialStructure};
+use halton::Sequence;
+use rand::prelude::*;
+use std::time::Duration;
+
+const WORLD_BOUNDS: Vec3 = Vec3::new(200., 200., 200.);
+const NEIGHBOR_CAP: usize = 100;
+const BOID_BOUNDARY_SIZE: f32 = 30.;
+const BOID_COUNT: i32 = 256;
+const BOID_SIZE: f32 = 1.5;
+const BOID_VIS_RANGE: f32 = 12.;
+const VIS_RANGE_SQ: f32 = BOID_VIS_RANGE * BOID_VIS_RANGE;
+const BOID_PROT_RANGE: f32 = 3.;
+// https://en.wikipedia.org/wiki/Bird_vision#Extraocular_anatomy
+const BOID_FOV: f32 = 120. * std::f32::consts::PI / 180.;
+const PROT_RANGE_SQ: f32 = BOID_PROT_RANGE * BOID_PROT_RANGE;
+const BOID_CENTER_FACTOR: f32 = 0.0005;
+const BOID_MATCHING_FACTOR: f32 = 0.05;
+const BOID_AVOID_FACTOR: f32 = 0.05;
+const BOID_TURN_FACTOR: f32 = 0.2;
+const BOID_MIN_SPEED: f32 = 2.0;
+const BOID_MAX_SPEED: f32 = 4.0;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    resolution: (800., 600.).into(),
+                    resizable: true,
+                    ..default()
+                }),
+                ..default()
+            }),
+            // Track boids in the KD-Tree
+            AutomaticUpdate::<Boid>::new()
+                .with_spatial_ds(SpatialStructure::KDTree3)
+                .with_frequency(Duration::from_millis(16)),
+        ))
+        .insert_resource(Time::<Fixed>::from_hz(60.0))
+        .add_event::<DvEvent>()
+        .add_systems(Startup, setup)
+        .add_systems(
+            FixedUpdate,
+            (flocking_system, velocity_system, movement_system).chain(),
+        )
+        .add_systems(Update, draw_boundary_gizmo)
+        .run();
+}
+
+#[derive(Component, Default)]
+struct Velocity(Vec3);
+
+impl Velocity {
+    pub fn random() -> Self {
+        let mut rng = rand::rng();
+        Velocity(Vec3::new(
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+        ))
+    }
+}
+
+// Marker for entities tracked by KDTree
+#[derive(Component, Default)]
+#[require(Velocity, Mesh3d, MeshMaterial3d<StandardMaterial>, Transform)]
+struct Boid;
+
+impl Boid {
+    pub fn mesh(meshes: &mut ResMut<Assets<Mesh>>) -> Mesh3d {
+        Mesh3d(meshes.add(Cone::new(0.5, 1.5)))
+    }
+}
+
+// Event for a change of velocity on some boid
+#[derive(Event)]
+struct DvEvent(Entity, Vec3);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0., 0., WORLD_BOUNDS.z).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::default().looking_to(Vec3::new(-1., -1., -1.), Vec3::Y),
+    ));
+
+    let mut rng = rand::rng();
+
+    // Halton sequence for Boid spawns, one base per axis
+    let seq = halton::Sequence::new(2)
+        .zip(Sequence::new(3))
+        .zip(Sequence::new(5))
+        .zip(1..BOID_COUNT);
+
+    let mesh = Boid::mesh(&mut meshes);
+
+    for (((x, y), z), _idx) in seq {
+        let spawn = Vec3::new(
+            (x as f32 - 0.5) * WORLD_BOUNDS.x,
+            (y as f32 - 0.5) * WORLD_BOUNDS.y,
+            (z as f32 - 0.5) * WORLD_BOUNDS.z,
+        );
+
+        let transform = Transform::from_translation(spawn).with_scale(Vec3::splat(BOID_SIZE));
+        let velocity = Velocity::random();
+        let material = MeshMaterial3d(materials.add(StandardMaterial::from_color(Color::hsl(
+            360. * rng.random::<f32>(),
+            rng.random(),
+            0.7,
+        ))));
+
+        commands.spawn((mesh.clone(), material, velocity, transform, Boid));
+    }
+}
+
+fn draw_boundary_gizmo(mut gizmos: Gizmos) {
+    gizmos.cuboid(
+        Transform::from_scale(Vec3::new(
+            WORLD_BOUNDS.x - BOID_BOUNDARY_SIZE,
+            WORLD_BOUNDS.y - BOID_BOUNDARY_SIZE,
+            WORLD_BOUNDS.z - BOID_BOUNDARY_SIZE,
+        )),
+        Color::srgb(0.5, 0.5, 0.5),
+    );
+}
+
+fn flocking_dv(
+    kdtree: &Res<KDTree3<Boid>>,
+    boid_query: &Query<(Entity, &Velocity, &Transform), With<Boid>>,
+    boid: &Entity,
+    t0: &&Transform,
+) -> Vec3 {
+    // https://vanhunteradams.com/Pico/Animal_Movement/Boids-algorithm.html
+    let mut dv = Vec3::default();
+    let mut vec_away = Vec3::default();
+    let mut avg_position = Vec3::default();
+    let mut avg_velocity = Vec3::default();
+    let mut neighboring_boids = 0;
+    let mut close_boids = 0;
+
+    for (_, entity) in kdtree.k_nearest_neighbour(t0.translation, NEIGHBOR_CAP) {
+        // The KD-tree rebuilds on its own timer and can briefly lag behind
+        // the world; just skip an entity it lists that no longer exists.
+        let Ok((other, v1, t1)) = boid_query.get(entity.unwrap()) else {
+            continue;
+        };
+
+        // Don't evaluate against itself
+        if *boid == other {
+            continue;
+        }
+
+        let vec_to = t1.translation - t0.translation;
+        let dist_sq = vec_to.length_squared();
+
+        // Don't evaluate boids out of range
+        if dist_sq > VIS_RANGE_SQ {
+            continue;
+        }
+
+        // Don't evaluate boids behind
+        if let Some(vec_to_norm) = vec_to.try_normalize() {
+            let heading = t0.forward();
+            if heading.angle_between(vec_to_norm) > BOID_FOV {
+                continue;
+            }
+        }
+
+        if dist_sq < PROT_RANGE_SQ {
+            // separation
+            vec_away -= vec_to;
+            close_boids += 1;
+        } else {
+            // cohesion
+            avg_position += vec_to;
+            // alignment
+            avg_velocity += v1.0;
+            neighboring_boids += 1;
+        }
+    }
+
+    if neighboring_boids > 0 {
+        let neighbors = neighboring_boids as f32;
+        dv += avg_position / neighbors * BOID_CENTER_FACTOR;
+        dv += avg_velocity / neighbors * BOID_MATCHING_FACTOR;
+    }
+
+    if close_boids > 0 {
+        let close = close_boids as f32;
+        dv += vec_away / close * BOID_AVOID_FACTOR;
+    }
+
+    dv
+}
+
+fn flocking_system(
+    boid_query: Query<(Entity, &Velocity, &Transform), With<Boid>>,
+    kdtree: Res<KDTree3<Boid>>,
+    mut dv_event_writer: EventWriter<DvEvent>,
+) {
+    let pool = ComputeTaskPool::get();
+    let boids = boid_query.iter().collect::<Vec<_>>();
+    let boids_per_thread = boids.len().div_ceil(pool.thread_num());
+
+    // https://docs.rs/bevy/latest/bevy/tasks/struct.ComputeTaskPool.html
+    // https://github.com/kvietcong/rusty-boids
+    for batch in pool.scope(|s| {
+        for chunk in boids.chunks(boids_per_thread) {
+            let kdtree = &kdtree;
+            let boid_query = &boid_query;
+
+            s.spawn(async move {
+                let mut dv_batch: Vec<DvEvent> = vec![];
+
+                for (boid, _, t0) in chunk {
+                    dv_batch.push(DvEvent(*boid, flocking_dv(kdtree, boid_query, boid, t0)));
+                }
+
+                dv_batch
+            });
+        }
+    }) {
+        dv_event_writer.send_batch(batch);
+    }
+}
+
+fn velocity_system(mut events: EventReader<DvEvent>, mut boids: Query<(&mut Velocity, &mut Transform)>) {
+    for DvEvent(boid, dv) in events.read() {
+        let Ok((mut velocity, transform)) = boids.get_mut(*boid) else {
+            continue;
+        };
+
+        velocity.0 += *dv;
+
+        let half = (WORLD_BOUNDS - Vec3::splat(BOID_BOUNDARY_SIZE)) / 2.;
+
+        // Steer back into the box
+        for axis in 0..3 {
+            if transform.translation[axis] < -half[axis] {
+                velocity.0[axis] += BOID_TURN_FACTOR;
+            }
+            if transform.translation[axis] > half[axis] {
+                velocity.0[axis] -= BOID_TURN_FACTOR;
+            }
+        }
+
+        // Clamp speed
+        let speed = velocity.0.length();
+
+        if speed < BOID_MIN_SPEED {
+            velocity.0 *= BOID_MIN_SPEED / speed;
+        }
+        if speed > BOID_MAX_SPEED {
+            velocity.0 *= BOID_MAX_SPEED / speed;
+        }
+    }
+}
+
+fn movement_system(mut query: Query<(&mut Velocity, &mut Transform)>) {
+    for (velocity, mut transform) in query.iter_mut() {
+        if let Some(heading) = velocity.0.try_normalize() {
+            transform.look_to(heading, Vec3::Y);
+        }
+        transform.translation += velocity.0;
+    }
+}
@@ -1,34 +1,108 @@
 use bevy::{
-    color::palettes::css::GRAY,
+    color::palettes::css::{BLUE, FUCHSIA, GRAY, GREEN, ORANGE, RED, YELLOW},
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
     input::common_conditions::input_just_released,
     math::Vec3Swizzles,
     prelude::*,
     render::{mesh::*, render_asset::RenderAssetUsages},
     tasks::ComputeTaskPool,
 };
+use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::ResourceInspectorPlugin};
 use bevy_spatial::{kdtree::KDTree2, AutomaticUpdate, SpatialAccess, SpatialStructure};
 use halton::Sequence;
 use rand::prelude::*;
 use std::time::Duration;
 
 const WINDOW_BOUNDS: Vec2 = Vec2::new(800., 400.);
-const NEIGHBOR_CAP: usize = 100;
 const BOID_BOUNDARY_SIZE: f32 = 150.;
 const BOID_COUNT: i32 = 256;
 const BOID_SIZE: f32 = 7.5;
-const BOID_VIS_RANGE: f32 = 40.;
-const VIS_RANGE_SQ: f32 = BOID_VIS_RANGE * BOID_VIS_RANGE;
-const BOID_PROT_RANGE: f32 = 8.;
-// https://en.wikipedia.org/wiki/Bird_vision#Extraocular_anatomy
-const BOID_FOV: f32 = 120. * std::f32::consts::PI / 180.;
-const PROT_RANGE_SQ: f32 = BOID_PROT_RANGE * BOID_PROT_RANGE;
-const BOID_CENTER_FACTOR: f32 = 0.0005;
-const BOID_MATCHING_FACTOR: f32 = 0.05;
-const BOID_AVOID_FACTOR: f32 = 0.05;
+const PREDATOR_COUNT: i32 = 4;
+const PREDATOR_SIZE: f32 = 11.0;
 const BOID_TURN_FACTOR: f32 = 0.2;
-const BOID_MOUSE_CHASE_FACTOR: f32 = 0.0005;
-const BOID_MIN_SPEED: f32 = 2.0;
-const BOID_MAX_SPEED: f32 = 4.0;
+
+// Tunable flocking parameters, exposed live through the egui inspector panel
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+struct BoidConfig {
+    neighbor_cap: usize,
+    vis_range: f32,
+    prot_range: f32,
+    // https://en.wikipedia.org/wiki/Bird_vision#Extraocular_anatomy
+    fov: f32,
+    center_factor: f32,
+    matching_factor: f32,
+    avoid_factor: f32,
+    min_speed: f32,
+    max_speed: f32,
+    spawn_burst: u32,
+    obstacle_lookahead: f32,
+    obstacle_avoid_range: f32,
+    obstacle_avoid_factor: f32,
+    flee_factor: f32,
+    chase_factor: f32,
+}
+
+impl Default for BoidConfig {
+    fn default() -> Self {
+        Self {
+            neighbor_cap: 100,
+            vis_range: 40.,
+            prot_range: 8.,
+            fov: 120. * std::f32::consts::PI / 180.,
+            center_factor: 0.0005,
+            matching_factor: 0.05,
+            avoid_factor: 0.05,
+            min_speed: 2.0,
+            max_speed: 4.0,
+            spawn_burst: 4,
+            obstacle_lookahead: 30.,
+            obstacle_avoid_range: 20.,
+            obstacle_avoid_factor: 0.5,
+            flee_factor: 0.15,
+            chase_factor: 0.002,
+        }
+    }
+}
+
+// Prey flock together and flee predators; predators ignore each other and chase prey
+#[derive(Component, Clone, Copy, PartialEq, Eq, Default)]
+enum Species {
+    #[default]
+    Prey,
+    Predator,
+}
+
+// Fired when a predator catches a prey, so despawn/respawn happens outside the parallel flocking pass
+#[derive(Event)]
+struct CatchEvent(Entity);
+
+// Halton sequence for respawned prey, distinct from `setup`'s spawn sequence
+#[derive(Resource)]
+struct RespawnSequence {
+    x: Sequence,
+    y: Sequence,
+}
+
+// Static geometry boids steer around; see `avoid_obstacles_dv`
+#[derive(Component)]
+struct Obstacle {
+    radius: f32,
+}
+
+// Mesh shared by every boid, spawned once in `setup` and reused on respawn
+#[derive(Resource)]
+struct SharedBoidMesh(Mesh2d);
+
+// Live boid count, shown in the stats overlay alongside FPS
+#[derive(Resource, Default)]
+struct BoidCounter {
+    count: u32,
+}
+
+// Marker for the FPS / boid-count overlay text.
+#[derive(Component)]
+struct StatsText;
 
 fn main() {
     App::new()
@@ -47,18 +121,30 @@ fn main() {
                 // TODO: check perf of other tree types
                 .with_spatial_ds(SpatialStructure::KDTree2)
                 .with_frequency(Duration::from_millis(16)),
+            EguiPlugin {
+                enable_multipass_for_primary_context: true,
+            },
+            ResourceInspectorPlugin::<BoidConfig>::default(),
+            FrameTimeDiagnosticsPlugin::default(),
         ))
         .insert_resource(Time::<Fixed>::from_hz(60.0))
+        .init_resource::<BoidConfig>()
+        .register_type::<BoidConfig>()
         .add_event::<DvEvent>()
+        .add_event::<CatchEvent>()
         .add_systems(Startup, setup)
         .add_systems(
             FixedUpdate,
-            (flocking_system, velocity_system, movement_system).chain(),
+            (flocking_system, catch_system, velocity_system, movement_system).chain(),
         )
         .add_systems(
             Update,
             (
                 draw_boid_gizmos,
+                draw_debug_gizmos,
+                toggle_debug_boid,
+                spawn_boids_system,
+                update_stats_text,
                 exit.run_if(input_just_released(KeyCode::Escape)),
             ),
         )
@@ -80,7 +166,7 @@ impl Velocity {
 
 // Marker for entities tracked by KDTree
 #[derive(Component, Default)]
-#[require(Velocity, Mesh2d, MeshMaterial2d<ColorMaterial>, Transform)]
+#[require(Velocity, Mesh2d, MeshMaterial2d<ColorMaterial>, Transform, Species)]
 struct Boid;
 
 impl Boid {
@@ -103,9 +189,34 @@ impl Boid {
     }
 }
 
-// Event for a change of velocity on some boid
+// Marker enabling the gizmo overlay below for a single boid at a time
+#[derive(Component, Default)]
+struct DrawDebug;
+
+// Snapshot of the steering terms `flocking_dv` computed for a `DrawDebug` boid
+#[derive(Component, Default, Clone)]
+struct DebugInfo {
+    neighbors: Vec<Vec2>,
+    cohesion: Vec2,
+    alignment: Vec2,
+    separation: Vec2,
+    flee: Vec2,
+    chase: Vec2,
+    obstacle: Vec2,
+}
+
+// Event for a change of velocity on some boid, with debug info if it's tracked
 #[derive(Event)]
-struct DvEvent(Entity, Vec2);
+struct DvEvent(Entity, Vec2, Option<DebugInfo>);
+
+// Per-boid inputs to `flocking_dv`, bundled to stay under clippy's too-many-arguments threshold
+struct BoidState<'a> {
+    entity: Entity,
+    velocity: &'a Velocity,
+    transform: &'a Transform,
+    species: &'a Species,
+    debug: bool,
+}
 
 fn setup(
     mut commands: Commands,
@@ -124,6 +235,7 @@ fn setup(
 
     let res = &window.resolution;
     let mesh = Boid::mesh(&mut meshes);
+    let mut spawned = 0u32;
 
     for ((x, y), idx) in seq {
         let spawn_x = (x as f32 * res.width()) - res.width() / 2.0;
@@ -143,7 +255,67 @@ fn setup(
         ));
 
         commands.spawn((mesh.clone(), material, velocity, transform, Boid));
+        spawned += 1;
+    }
+
+    // A handful of predators, hunting the prey flock instead of each other.
+    let predator_material = MeshMaterial2d(materials.add(Color::from(RED)));
+    for _ in 0..PREDATOR_COUNT {
+        let spawn_x = rng.random_range(-res.width() / 2.0..res.width() / 2.0);
+        let spawn_y = rng.random_range(-res.height() / 2.0..res.height() / 2.0);
+        let transform =
+            Transform::from_xyz(spawn_x, spawn_y, 1.0).with_scale(Vec3::splat(PREDATOR_SIZE));
+        let velocity = Velocity::random();
+
+        commands.spawn((
+            mesh.clone(),
+            predator_material.clone(),
+            velocity,
+            transform,
+            Boid,
+            Species::Predator,
+        ));
+        spawned += 1;
     }
+
+    commands.insert_resource(SharedBoidMesh(mesh));
+    commands.insert_resource(BoidCounter { count: spawned });
+    commands.insert_resource(RespawnSequence {
+        x: Sequence::new(7),
+        y: Sequence::new(11),
+    });
+
+    // A handful of static obstacles for the flock to steer around.
+    let obstacle_material = MeshMaterial2d(materials.add(Color::from(GRAY)));
+    for (dx, dy, radius) in [
+        (-0.2, 0.0, 25.),
+        (0.2, 0.15, 18.),
+        (0.2, -0.2, 15.),
+    ] {
+        let obstacle_mesh = Mesh2d(meshes.add(Circle::new(radius)));
+        commands.spawn((
+            obstacle_mesh,
+            obstacle_material.clone(),
+            Transform::from_xyz(dx * res.width(), dy * res.height(), 0.),
+            Obstacle { radius },
+        ));
+    }
+
+    commands.spawn((
+        Text::new("FPS: --\nBoids: --"),
+        TextFont {
+            font_size: 16.,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.),
+            left: Val::Px(8.),
+            ..default()
+        },
+        StatsText,
+    ));
 }
 
 fn draw_boid_gizmos(window: Single<&Window>, mut gizmos: Gizmos) {
@@ -165,14 +337,52 @@ fn angle_towards(a: Vec2, b: Vec2) -> f32 {
     dir.y.atan2(dir.x)
 }
 
+// Repulsion perpendicular to heading, for obstacles within the lookahead or avoid range
+fn avoid_obstacles_dv(
+    config: &Res<BoidConfig>,
+    obstacles: &Query<(&Transform, &Obstacle)>,
+    pos: Vec2,
+    heading: Vec2,
+) -> Vec2 {
+    let lookahead = pos + heading * config.obstacle_lookahead;
+    let perpendicular = Vec2::new(-heading.y, heading.x);
+    let mut dv = Vec2::default();
+
+    for (transform, obstacle) in obstacles {
+        let inflated = obstacle.radius + BOID_SIZE;
+        let to_obstacle = transform.translation.xy() - pos;
+        let dist = to_obstacle.length();
+        let lookahead_dist = (transform.translation.xy() - lookahead).length();
+
+        if dist > config.obstacle_avoid_range && lookahead_dist > inflated {
+            continue;
+        }
+
+        let side = if to_obstacle.dot(perpendicular) >= 0. {
+            -perpendicular
+        } else {
+            perpendicular
+        };
+        dv += side * (config.obstacle_avoid_factor / dist.max(1.));
+    }
+
+    dv
+}
+
 fn flocking_dv(
+    config: &Res<BoidConfig>,
     kdtree: &Res<KDTree2<Boid>>,
-    boid_query: &Query<(Entity, &Velocity, &Transform), With<Boid>>,
-    camera: &Single<(&Camera, &GlobalTransform)>,
-    window: &Single<&Window>,
-    boid: &Entity,
-    t0: &&Transform,
-) -> Vec2 {
+    boid_query: &Query<(Entity, &Velocity, &Transform, &Species), With<Boid>>,
+    obstacles: &Query<(&Transform, &Obstacle)>,
+    state: &BoidState,
+) -> (Vec2, Option<DebugInfo>, Vec<Entity>) {
+    let (boid, v0, t0, s0, debug) = (
+        &state.entity,
+        state.velocity,
+        state.transform,
+        state.species,
+        state.debug,
+    );
     // https://vanhunteradams.com/Pico/Animal_Movement/Boids-algorithm.html
     let mut dv = Vec2::default();
     let mut vec_away = Vec2::default();
@@ -180,10 +390,21 @@ fn flocking_dv(
     let mut avg_velocity = Vec2::default();
     let mut neighboring_boids = 0;
     let mut close_boids = 0;
-
-    for (_, entity) in kdtree.k_nearest_neighbour(t0.translation.xy(), NEIGHBOR_CAP) {
-        let Ok((other, v1, t1)) = boid_query.get(entity.unwrap()) else {
-            todo!()
+    let mut flee_sum = Vec2::default();
+    let mut fleeing = 0;
+    let mut avg_prey_position = Vec2::default();
+    let mut prey_seen = 0;
+    let mut caught = Vec::new();
+    let mut debug_neighbors = Vec::new();
+
+    let vis_range_sq = config.vis_range * config.vis_range;
+    let prot_range_sq = config.prot_range * config.prot_range;
+
+    for (_, entity) in kdtree.k_nearest_neighbour(t0.translation.xy(), config.neighbor_cap) {
+        // The KD-tree rebuilds on its own timer, so it can still list a boid
+        // a predator despawned in an earlier tick; just skip it.
+        let Ok((other, v1, t1, s1)) = boid_query.get(entity.unwrap()) else {
+            continue;
         };
 
         // Don't evaluate against itself
@@ -195,105 +416,237 @@ fn flocking_dv(
         let dist_sq = vec_to.x * vec_to.x + vec_to.y * vec_to.y;
 
         // Don't evaluate boids out of range
-        if dist_sq > VIS_RANGE_SQ {
+        if dist_sq > vis_range_sq {
             continue;
         }
 
+        // Catches happen on contact regardless of facing, so check this
+        // before the FOV gate below rules out prey behind the predator.
+        if *s0 == Species::Predator && *s1 == Species::Prey && dist_sq < prot_range_sq {
+            caught.push(other);
+        }
+
         // Don't evaluate boids behind
         if let Some(vec_to_norm) = vec_to.try_normalize() {
             if t0
                 .rotation
                 .angle_between(Quat::from_rotation_arc_2d(Vec2::X, vec_to_norm))
-                > BOID_FOV
+                > config.fov
             {
                 continue;
             }
         }
 
-        if dist_sq < PROT_RANGE_SQ {
-            // separation
-            vec_away -= vec_to;
-            close_boids += 1;
-        } else {
-            // cohesion
-            avg_position += vec_to;
-            // alignment
-            avg_velocity += v1.0;
-            neighboring_boids += 1;
+        // Only count neighbors that actually contribute a steering term
+        // below, so the debug overlay's neighbor lines match its arrows.
+        match (s0, s1) {
+            (Species::Prey, Species::Predator) => {
+                flee_sum -= vec_to;
+                fleeing += 1;
+                if debug {
+                    debug_neighbors.push(t1.translation.xy());
+                }
+            }
+            (Species::Predator, Species::Prey) => {
+                avg_prey_position += vec_to;
+                prey_seen += 1;
+                if debug {
+                    debug_neighbors.push(t1.translation.xy());
+                }
+            }
+            (Species::Prey, Species::Prey) => {
+                if dist_sq < prot_range_sq {
+                    // separation
+                    vec_away -= vec_to;
+                    close_boids += 1;
+                } else {
+                    // cohesion
+                    avg_position += vec_to;
+                    // alignment
+                    avg_velocity += v1.0;
+                    neighboring_boids += 1;
+                }
+                if debug {
+                    debug_neighbors.push(t1.translation.xy());
+                }
+            }
+            (Species::Predator, Species::Predator) => {}
         }
     }
 
-    if neighboring_boids > 0 {
-        let neighbors = neighboring_boids as f32;
-        dv += avg_position / neighbors * BOID_CENTER_FACTOR;
-        dv += avg_velocity / neighbors * BOID_MATCHING_FACTOR;
-    }
+    let mut cohesion = Vec2::default();
+    let mut alignment = Vec2::default();
+    let mut separation = Vec2::default();
+    let mut flee = Vec2::default();
+    let mut chase = Vec2::default();
+
+    match s0 {
+        Species::Prey => {
+            if neighboring_boids > 0 {
+                let neighbors = neighboring_boids as f32;
+                cohesion = avg_position / neighbors * config.center_factor;
+                alignment = avg_velocity / neighbors * config.matching_factor;
+                dv += cohesion;
+                dv += alignment;
+            }
 
-    if close_boids > 0 {
-        let close = close_boids as f32;
-        dv += vec_away / close * BOID_AVOID_FACTOR;
-    }
+            if close_boids > 0 {
+                let close = close_boids as f32;
+                separation = vec_away / close * config.avoid_factor;
+                dv += separation;
+            }
 
-    // Chase the mouse
-    let (camera, t_camera) = **camera;
-    if let Some(c_window) = window.cursor_position() {
-        if let Ok(c_world) = camera.viewport_to_world_2d(t_camera, c_window) {
-            let to_cursor = c_world - t0.translation.xy();
-            dv += to_cursor * BOID_MOUSE_CHASE_FACTOR;
+            if fleeing > 0 {
+                let fleeing = fleeing as f32;
+                flee = flee_sum / fleeing * config.flee_factor;
+                dv += flee;
+            }
+        }
+        Species::Predator => {
+            if prey_seen > 0 {
+                let prey = prey_seen as f32;
+                chase = avg_prey_position / prey * config.chase_factor;
+                dv += chase;
+            }
         }
     }
 
-    dv
+    let mut obstacle = Vec2::default();
+    if let Some(heading) = v0.0.try_normalize() {
+        obstacle = avoid_obstacles_dv(config, obstacles, t0.translation.xy(), heading);
+        dv += obstacle;
+    }
+
+    let debug_info = debug.then(|| DebugInfo {
+        neighbors: debug_neighbors,
+        cohesion,
+        alignment,
+        separation,
+        flee,
+        chase,
+        obstacle,
+    });
+
+    (dv, debug_info, caught)
 }
 
 fn flocking_system(
-    boid_query: Query<(Entity, &Velocity, &Transform), With<Boid>>,
+    config: Res<BoidConfig>,
+    boid_query: Query<(Entity, &Velocity, &Transform, &Species), With<Boid>>,
+    debug_query: Query<Entity, With<DrawDebug>>,
+    obstacles: Query<(&Transform, &Obstacle)>,
     kdtree: Res<KDTree2<Boid>>,
     mut dv_event_writer: EventWriter<DvEvent>,
-    camera: Single<(&Camera, &GlobalTransform)>,
-    window: Single<&Window>,
+    mut catch_event_writer: EventWriter<CatchEvent>,
 ) {
     let pool = ComputeTaskPool::get();
     let boids = boid_query.iter().collect::<Vec<_>>();
     let boids_per_thread = boids.len().div_ceil(pool.thread_num());
+    let debugged = debug_query.iter().collect::<std::collections::HashSet<_>>();
 
     // https://docs.rs/bevy/latest/bevy/tasks/struct.ComputeTaskPool.html
     // https://github.com/kvietcong/rusty-boids
-    for batch in pool.scope(|s| {
+    for (batch, caught) in pool.scope(|s| {
         for chunk in boids.chunks(boids_per_thread) {
+            let config = &config;
             let kdtree = &kdtree;
             let boid_query = &boid_query;
-            let camera = &camera;
-            let window = &window;
+            let obstacles = &obstacles;
+            let debugged = &debugged;
 
             s.spawn(async move {
                 let mut dv_batch: Vec<DvEvent> = vec![];
-
-                for (boid, _, t0) in chunk {
-                    dv_batch.push(DvEvent(
-                        *boid,
-                        flocking_dv(kdtree, boid_query, camera, window, boid, t0),
-                    ));
+                let mut caught_batch: Vec<Entity> = vec![];
+
+                for (boid, v0, t0, s0) in chunk {
+                    let state = BoidState {
+                        entity: *boid,
+                        velocity: v0,
+                        transform: t0,
+                        species: s0,
+                        debug: debugged.contains(boid),
+                    };
+                    let (dv, debug_info, caught) =
+                        flocking_dv(config, kdtree, boid_query, obstacles, &state);
+                    dv_batch.push(DvEvent(*boid, dv, debug_info));
+                    caught_batch.extend(caught);
                 }
 
-                dv_batch
+                (dv_batch, caught_batch)
             });
         }
     }) {
         dv_event_writer.send_batch(batch);
+        catch_event_writer.send_batch(caught);
+    }
+}
+
+// Despawns caught prey and respawns them at the next `RespawnSequence` point
+fn catch_system(
+    mut commands: Commands,
+    mut events: EventReader<CatchEvent>,
+    mut sequence: ResMut<RespawnSequence>,
+    mesh: Res<SharedBoidMesh>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    window: Single<&Window>,
+) {
+    let mut caught = std::collections::HashSet::new();
+    for CatchEvent(prey) in events.read() {
+        caught.insert(*prey);
+    }
+
+    if caught.is_empty() {
+        return;
+    }
+
+    let res = &window.resolution;
+    let mut rng = rand::rng();
+
+    for prey in caught {
+        commands.entity(prey).despawn();
+
+        let x = sequence.x.next().unwrap_or(0.5) as f32;
+        let y = sequence.y.next().unwrap_or(0.5) as f32;
+        let transform = Transform::from_xyz(
+            (x - 0.5) * res.width(),
+            (y - 0.5) * res.height(),
+            0.,
+        )
+        .with_scale(Vec3::splat(BOID_SIZE));
+        let velocity = Velocity::random();
+        let material = MeshMaterial2d(materials.add(
+            Color::hsl(360. * rng.random::<f32>(), rng.random(), 0.7),
+        ));
+
+        commands.spawn((
+            mesh.0.clone(),
+            material,
+            velocity,
+            transform,
+            Boid,
+            Species::Prey,
+        ));
     }
 }
 
 fn velocity_system(
+    mut commands: Commands,
+    config: Res<BoidConfig>,
     mut events: EventReader<DvEvent>,
     mut boids: Query<(&mut Velocity, &mut Transform)>,
     window: Single<&Window>,
 ) {
-    for DvEvent(boid, dv) in events.read() {
+    for DvEvent(boid, dv, debug_info) in events.read() {
+        // A predator may have caught and despawned this boid earlier in the
+        // same tick's `catch_system`; its queued `DvEvent` is now stale.
         let Ok((mut velocity, transform)) = boids.get_mut(*boid) else {
-            todo!()
+            continue;
         };
 
+        if let Some(debug_info) = debug_info {
+            commands.entity(*boid).insert(debug_info.clone());
+        }
+
         velocity.0.x += dv.x;
         velocity.0.y += dv.y;
 
@@ -319,11 +672,11 @@ fn velocity_system(
         // Clamp speed
         let speed = velocity.0.length();
 
-        if speed < BOID_MIN_SPEED {
-            velocity.0 *= BOID_MIN_SPEED / speed;
+        if speed < config.min_speed {
+            velocity.0 *= config.min_speed / speed;
         }
-        if speed > BOID_MAX_SPEED {
-            velocity.0 *= BOID_MAX_SPEED / speed;
+        if speed > config.max_speed {
+            velocity.0 *= config.max_speed / speed;
         }
     }
 }
@@ -339,3 +692,140 @@ fn movement_system(mut query: Query<(&mut Velocity, &mut Transform)>) {
 fn exit(mut exit: EventWriter<AppExit>) {
     exit.send(AppExit::Success);
 }
+
+// Draws the vision/protected-range circles, FOV, neighbor lines and steering
+// arrows for whichever boid is wearing `DrawDebug`.
+fn draw_debug_gizmos(
+    config: Res<BoidConfig>,
+    boids: Query<(&Transform, &Velocity, Option<&DebugInfo>), With<DrawDebug>>,
+    mut gizmos: Gizmos,
+) {
+    for (transform, velocity, debug_info) in &boids {
+        let pos = transform.translation.xy();
+
+        gizmos.circle_2d(Isometry2d::from_translation(pos), config.vis_range, GRAY);
+        gizmos.circle_2d(Isometry2d::from_translation(pos), config.prot_range, RED);
+
+        // `flocking_dv` treats `config.fov` as the max one-sided deviation
+        // from heading (glam's `angle_between` is unsigned), so the cone
+        // spans twice `fov`.
+        let heading = velocity.0.try_normalize().unwrap_or(Vec2::X);
+        for sign in [-1., 1.] {
+            let edge = Vec2::from_angle(config.fov * sign).rotate(heading);
+            gizmos.line_2d(pos, pos + edge * config.vis_range, YELLOW);
+        }
+
+        let Some(debug_info) = debug_info else {
+            continue;
+        };
+
+        for neighbor in &debug_info.neighbors {
+            gizmos.line_2d(pos, *neighbor, GRAY.with_alpha(0.3));
+        }
+
+        // Contributions are tiny per-frame deltas; scale them up so the
+        // arrows are actually visible against the boid's own size.
+        const STEERING_SCALE: f32 = 200.;
+        gizmos.arrow_2d(pos, pos + debug_info.cohesion * STEERING_SCALE, GREEN);
+        gizmos.arrow_2d(pos, pos + debug_info.alignment * STEERING_SCALE, YELLOW);
+        gizmos.arrow_2d(pos, pos + debug_info.separation * STEERING_SCALE, RED);
+        gizmos.arrow_2d(pos, pos + debug_info.flee * STEERING_SCALE, BLUE);
+        gizmos.arrow_2d(pos, pos + debug_info.chase * STEERING_SCALE, FUCHSIA);
+        gizmos.arrow_2d(pos, pos + debug_info.obstacle * STEERING_SCALE, ORANGE);
+    }
+}
+
+// While the left mouse button is held, spawns `spawn_burst` boids at the cursor
+fn spawn_boids_system(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mesh: Res<SharedBoidMesh>,
+    mut counter: ResMut<BoidCounter>,
+    config: Res<BoidConfig>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    window: Single<&Window>,
+) {
+    if !mouse.pressed(MouseButton::Left) {
+        return;
+    }
+
+    let (camera, t_camera) = *camera;
+    let Some(c_window) = window.cursor_position() else {
+        return;
+    };
+    let Ok(c_world) = camera.viewport_to_world_2d(t_camera, c_window) else {
+        return;
+    };
+
+    let mut rng = rand::rng();
+
+    for _ in 0..config.spawn_burst {
+        let velocity = Velocity::random();
+        let material = MeshMaterial2d(
+            materials.add(Color::hsl(360. * rng.random::<f32>(), rng.random(), 0.7)),
+        );
+        let transform =
+            Transform::from_xyz(c_world.x, c_world.y, 0.).with_scale(Vec3::splat(BOID_SIZE));
+
+        commands.spawn((mesh.0.clone(), material, velocity, transform, Boid));
+        counter.count += 1;
+    }
+}
+
+// Drives the stats overlay from `FrameTimeDiagnosticsPlugin` and `BoidCounter`.
+fn update_stats_text(
+    diagnostics: Res<DiagnosticsStore>,
+    counter: Res<BoidCounter>,
+    mut text: Single<&mut Text, With<StatsText>>,
+) {
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or(0.0);
+
+    text.0 = format!("FPS: {fps:.0}\nBoids: {}", counter.count);
+}
+
+// Toggles the `DrawDebug` marker onto whichever boid is nearest the cursor,
+// clearing it off every other boid so only one is inspected at a time.
+fn toggle_debug_boid(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    window: Single<&Window>,
+    boids: Query<(Entity, &Transform), With<Boid>>,
+    debugged: Query<Entity, With<DrawDebug>>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyD) {
+        return;
+    }
+
+    let (camera, t_camera) = *camera;
+    let Some(c_window) = window.cursor_position() else {
+        return;
+    };
+    let Ok(c_world) = camera.viewport_to_world_2d(t_camera, c_window) else {
+        return;
+    };
+
+    let nearest = boids.iter().min_by(|(_, a), (_, b)| {
+        a.translation
+            .xy()
+            .distance_squared(c_world)
+            .total_cmp(&b.translation.xy().distance_squared(c_world))
+    });
+
+    let Some((nearest, _)) = nearest else {
+        return;
+    };
+
+    for entity in &debugged {
+        commands.entity(entity).remove::<DrawDebug>();
+        commands.entity(entity).remove::<DebugInfo>();
+    }
+
+    if !debugged.contains(nearest) {
+        commands.entity(nearest).insert(DrawDebug);
+    }
+}